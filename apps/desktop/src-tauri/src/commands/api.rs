@@ -1,4 +1,6 @@
-use super::ApiResponse;
+use super::{AppState, ApiResponse};
+use futures_util::StreamExt;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,6 +13,124 @@ pub struct ChatMessage {
 pub struct ChatResponse {
     pub message: String,
     pub citations: Vec<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+}
+
+/// A tool the assistant may call, expressed the way the backend's function-calling
+/// API expects: a name plus a JSON-schema description of its arguments.
+///
+/// Read-only tools are named with a `may_` prefix and are dispatched automatically;
+/// side-effecting tools are named with an `execute_` prefix and are left for the UI
+/// to gate behind a confirmation prompt; `dispatch_tool_call` refuses to run them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ToolResultMessage {
+    role: String,
+    name: String,
+    content: String,
+}
+
+/// Caps the number of tool-call round trips `send_chat_message` will make against
+/// the backend before giving up and returning whatever answer it has.
+const MAX_TOOL_ITERATIONS: u32 = 5;
+
+fn available_tools() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            name: "may_search_literature".to_string(),
+            description: "Search the medical literature index for papers relevant to a query."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Search terms" },
+                    "limit": { "type": "integer", "description": "Max results to return" }
+                },
+                "required": ["query", "limit"]
+            }),
+        },
+        ToolDefinition {
+            name: "may_get_voe_alerts".to_string(),
+            description: "Fetch current vaso-occlusive event risk alerts.".to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+    ]
+}
+
+/// Runs a tool call locally and returns its JSON result (as a string, ready to be
+/// embedded in a `{role: "tool"}` message) along with any citations it produced.
+///
+/// Tools prefixed `execute_` are side-effecting and are rejected here: the UI must
+/// obtain user confirmation and invoke them itself rather than have the loop in
+/// `send_chat_message` run them unattended.
+async fn dispatch_tool_call(
+    state: &AppState,
+    call: &ToolCall,
+) -> Result<(String, Vec<String>), String> {
+    if call.name.starts_with("execute_") {
+        return Err(format!(
+            "tool '{}' is side-effecting and requires user confirmation before it can run",
+            call.name
+        ));
+    }
+
+    match call.name.as_str() {
+        "may_search_literature" => {
+            let query = call.arguments["query"].as_str().unwrap_or_default();
+            let limit = call.arguments["limit"].as_u64().unwrap_or(10);
+            let url = state.endpoint("api/literature/search");
+            let results: Vec<LiteratureResult> = state
+                .client
+                .get(&url)
+                .query(&[("q", query), ("limit", &limit.to_string())])
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .json()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let citations = results
+                .iter()
+                .filter_map(|r| r.pmid.clone().or_else(|| r.doi.clone()))
+                .collect();
+            let content = serde_json::to_string(&results).map_err(|e| e.to_string())?;
+            Ok((content, citations))
+        }
+        "may_get_voe_alerts" => {
+            let url = state.endpoint("api/voe/alerts");
+            let alerts: Vec<VoeAlert> = state
+                .client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .json()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let content = serde_json::to_string(&alerts).map_err(|e| e.to_string())?;
+            Ok((content, Vec::new()))
+        }
+        other => Err(format!("unknown tool '{}'", other)),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,7 +142,7 @@ pub struct LiteratureResult {
     pub relevance_score: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoeAlert {
     pub id: String,
     pub patient_id: String,
@@ -32,11 +152,13 @@ pub struct VoeAlert {
 }
 
 #[tauri::command]
-pub async fn fetch_api_data(endpoint: String) -> Result<ApiResponse<serde_json::Value>, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://localhost:3001/api/{}", endpoint);
-    
-    match client.get(&url).send().await {
+pub async fn fetch_api_data(
+    endpoint: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ApiResponse<serde_json::Value>, String> {
+    let url = state.endpoint(&format!("api/{}", endpoint));
+
+    match state.client.get(&url).send().await {
         Ok(response) => {
             match response.json::<serde_json::Value>().await {
                 Ok(data) => Ok(ApiResponse {
@@ -60,46 +182,230 @@ pub async fn fetch_api_data(endpoint: String) -> Result<ApiResponse<serde_json::
 }
 
 #[tauri::command]
-pub async fn send_chat_message(message: ChatMessage, mode: String, tone: String) -> Result<ApiResponse<ChatResponse>, String> {
-    let client = reqwest::Client::new();
-    let url = "http://localhost:3001/api/chat";
-    
-    let body = serde_json::json!({
-        "message": message.content,
+pub async fn send_chat_message(
+    message: ChatMessage,
+    mode: String,
+    tone: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<ApiResponse<ChatResponse>, String> {
+    let url = state.endpoint("api/chat");
+    let tools = available_tools();
+
+    let mut history = vec![serde_json::json!({
         "role": message.role,
-        "mode": mode,
-        "tone": tone
-    });
-    
-    match client.post(url).json(&body).send().await {
-        Ok(response) => {
-            match response.json::<ChatResponse>().await {
-                Ok(data) => Ok(ApiResponse {
-                    success: true,
-                    data: Some(data),
-                    error: None,
-                }),
-                Err(e) => Ok(ApiResponse {
+        "content": message.content,
+    })];
+    let mut citations = Vec::new();
+    let mut last_data = None;
+
+    for _ in 0..MAX_TOOL_ITERATIONS {
+        let body = serde_json::json!({
+            "message": message.content,
+            "role": message.role,
+            "mode": mode,
+            "tone": tone,
+            "messages": history,
+            "tools": tools,
+        });
+
+        let response = match state.client.post(&url).json(&body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(ApiResponse {
                     success: false,
                     data: None,
                     error: Some(e.to_string()),
-                }),
+                })
+            }
+        };
+
+        let mut data = match response.json::<ChatResponse>().await {
+            Ok(data) => data,
+            Err(e) => {
+                return Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+
+        if data.tool_calls.is_empty() {
+            citations.append(&mut data.citations);
+            data.citations = citations;
+            return Ok(ApiResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            });
+        }
+
+        history.push(serde_json::json!({
+            "role": "assistant",
+            "content": data.message,
+            "tool_calls": data.tool_calls,
+        }));
+
+        for call in &data.tool_calls {
+            match dispatch_tool_call(&state, call).await {
+                Ok((content, mut call_citations)) => {
+                    citations.append(&mut call_citations);
+                    history.push(serde_json::to_value(ToolResultMessage {
+                        role: "tool".to_string(),
+                        name: call.name.clone(),
+                        content,
+                    }).map_err(|e| e.to_string())?);
+                }
+                Err(e) => {
+                    history.push(serde_json::to_value(ToolResultMessage {
+                        role: "tool".to_string(),
+                        name: call.name.clone(),
+                        content: format!("error: {}", e),
+                    }).map_err(|e| e.to_string())?);
+                }
             }
         }
-        Err(e) => Ok(ApiResponse {
+
+        last_data = Some(data);
+    }
+
+    // Iteration budget exhausted without a tool-call-free response: surface the
+    // last answer we did get (with whatever citations were gathered along the
+    // way) rather than throwing it away, since it's still the best answer we have.
+    match last_data {
+        Some(mut data) => {
+            citations.append(&mut data.citations);
+            data.citations = citations;
+            Ok(ApiResponse {
+                success: true,
+                data: Some(data),
+                error: Some(format!(
+                    "assistant did not finish tool calls within {} iterations; returning last partial answer",
+                    MAX_TOOL_ITERATIONS
+                )),
+            })
+        }
+        None => Ok(ApiResponse {
             success: false,
             data: None,
-            error: Some(e.to_string()),
+            error: Some(format!(
+                "assistant did not produce a final answer within {} tool-call iterations",
+                MAX_TOOL_ITERATIONS
+            )),
         }),
     }
 }
 
+/// Token sent down a chat streaming `Channel` as it is produced, and the terminal
+/// event once the backend has finished generating.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ChatStreamEvent {
+    Delta { content: String },
+    Done { citations: Vec<String> },
+    Error { message: String },
+}
+
+/// Streaming counterpart to `send_chat_message`. Instead of waiting for the full
+/// `ChatResponse` body, this reads the backend's chunked/SSE `/api/chat` response
+/// and forwards each token delta to `channel` as it arrives, so long medical
+/// answers render incrementally. Callers that want the old blocking behavior
+/// should keep calling the separate `send_chat_message` command; this one is
+/// only reached when the frontend opts into streaming.
+#[tauri::command]
+pub async fn send_chat_message_stream(
+    message: ChatMessage,
+    mode: String,
+    tone: String,
+    channel: tauri::ipc::Channel<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let url = state.endpoint("api/chat");
+
+    let body = serde_json::json!({
+        "message": message.content,
+        "role": message.role,
+        "mode": mode,
+        "tone": tone,
+        "stream": true,
+    });
+
+    let send_event = |event: ChatStreamEvent| -> Result<(), String> {
+        let payload = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+        channel.send(payload).map_err(|e| e.to_string())
+    };
+
+    let response = match state.client.post(&url).json(&body).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            send_event(ChatStreamEvent::Error { message: e.to_string() })?;
+            return Ok(());
+        }
+    };
+
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    let mut citations = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                send_event(ChatStreamEvent::Error { message: e.to_string() })?;
+                return Ok(());
+            }
+        };
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(pos) = buffer.find('\n') {
+            let line = buffer[..pos].trim().to_string();
+            buffer.drain(..=pos);
+
+            let Some(payload) = line.strip_prefix("data: ") else {
+                continue;
+            };
+            if payload.is_empty() || payload == "[DONE]" {
+                continue;
+            }
+
+            let event: serde_json::Value = match serde_json::from_str(payload) {
+                Ok(event) => event,
+                Err(e) => {
+                    send_event(ChatStreamEvent::Error { message: e.to_string() })?;
+                    continue;
+                }
+            };
+
+            if let Some(delta) = event.get("delta").and_then(|d| d.as_str()) {
+                send_event(ChatStreamEvent::Delta { content: delta.to_string() })?;
+            }
+            if let Some(new_citations) = event.get("citations").and_then(|c| c.as_array()) {
+                citations = new_citations
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect();
+            }
+        }
+    }
+
+    send_event(ChatStreamEvent::Done { citations })
+}
+
 #[tauri::command]
-pub async fn search_literature(query: String, limit: u32) -> Result<ApiResponse<Vec<LiteratureResult>>, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://localhost:3001/api/literature/search?q={}&limit={}", query, limit);
-    
-    match client.get(&url).send().await {
+pub async fn search_literature(
+    query: String,
+    limit: u32,
+    state: tauri::State<'_, AppState>,
+) -> Result<ApiResponse<Vec<LiteratureResult>>, String> {
+    let url = state.endpoint("api/literature/search");
+
+    match state
+        .client
+        .get(&url)
+        .query(&[("q", query.as_str()), ("limit", &limit.to_string())])
+        .send()
+        .await
+    {
         Ok(response) => {
             match response.json::<Vec<LiteratureResult>>().await {
                 Ok(data) => Ok(ApiResponse {
@@ -123,11 +429,12 @@ pub async fn search_literature(query: String, limit: u32) -> Result<ApiResponse<
 }
 
 #[tauri::command]
-pub async fn get_voe_alerts() -> Result<ApiResponse<Vec<VoeAlert>>, String> {
-    let client = reqwest::Client::new();
-    let url = "http://localhost:3001/api/voe/alerts";
-    
-    match client.get(url).send().await {
+pub async fn get_voe_alerts(
+    state: tauri::State<'_, AppState>,
+) -> Result<ApiResponse<Vec<VoeAlert>>, String> {
+    let url = state.endpoint("api/voe/alerts");
+
+    match state.client.get(&url).send().await {
         Ok(response) => {
             match response.json::<Vec<VoeAlert>>().await {
                 Ok(data) => Ok(ApiResponse {
@@ -148,4 +455,14 @@ pub async fn get_voe_alerts() -> Result<ApiResponse<Vec<VoeAlert>>, String> {
             error: Some(e.to_string()),
         }),
     }
+}
+
+/// Repoints the shared `AppState` at a different AngstromSCD backend without a
+/// recompile, for deployments that talk to a remote server instead of the local
+/// dev stack.
+#[tauri::command]
+pub fn set_backend_url(url: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let parsed = Url::parse(&url).map_err(|e| e.to_string())?;
+    *state.base_url.lock().unwrap() = parsed;
+    Ok(())
 }
\ No newline at end of file
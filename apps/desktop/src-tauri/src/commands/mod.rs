@@ -1,10 +1,14 @@
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 
 pub mod api;
 pub mod native;
+pub mod workspace;
 
 pub use api::*;
 pub use native::*;
+pub use workspace::*;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
@@ -13,6 +17,26 @@ pub struct ApiResponse<T> {
     pub error: Option<String>,
 }
 
+/// Shared state managed via `app.manage(...)`: a pooled `reqwest::Client` and the
+/// configured AngstromSCD backend URL. Replaces the old pattern of every command
+/// building its own client and hardcoding `http://localhost:3001`.
+pub struct AppState {
+    pub client: reqwest::Client,
+    pub base_url: Mutex<Url>,
+}
+
+impl AppState {
+    /// Resolves `path` (relative, e.g. `"api/chat"`) against the currently
+    /// configured base URL by concatenation rather than `Url::join`, since `join`
+    /// discards the last path segment of the base — which would silently drop a
+    /// mount point like `/v1` for backends deployed under a subpath.
+    pub fn endpoint(&self, path: &str) -> String {
+        let base = self.base_url.lock().unwrap();
+        let base = base.as_str().trim_end_matches('/');
+        format!("{}/{}", base, path.trim_start_matches('/'))
+    }
+}
+
 #[tauri::command]
 pub fn greet(name: &str) -> String {
     format!("Hello, {}! Welcome to AngstromSCD Medical Research Assistant.", name)
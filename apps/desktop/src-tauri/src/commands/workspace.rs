@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::webview::{Webview, WebviewBuilder};
+use tauri::{AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, WebviewUrl, Window};
+
+/// One pane in the multi-column research workspace (literature search, VOE
+/// alerts, a chat thread, ...). `position` is its left-to-right index and is
+/// kept dense and zero-based as columns are moved or closed. This is the
+/// shape sent to the frontend in `columns` events — the backing child
+/// `Webview` is kept server-side in `ColumnHandle`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Column {
+    pub id: String,
+    pub title: String,
+    pub position: u32,
+}
+
+/// A column plus the real child webview rendering it, embedded in the host
+/// window via `Window::add_child` and repositioned/resized as columns change
+/// or the host window is resized.
+struct ColumnHandle {
+    column: Column,
+    webview: Webview,
+}
+
+/// Holds the open columns (and their child webviews) so the frontend can
+/// persist layout across restarts and so every command mutates the same
+/// shared state. `scroll_offset` is how far the column strip has been
+/// scrolled horizontally, reported by the frontend via
+/// `set_workspace_scroll_offset`, and is subtracted from each column's
+/// nominal x position so the native child webviews track the scrolled host
+/// region instead of drifting out of alignment with it.
+#[derive(Default)]
+pub struct WorkspaceState {
+    columns: Mutex<Vec<ColumnHandle>>,
+    next_id: Mutex<u32>,
+    scroll_offset: Mutex<f64>,
+}
+
+const HOST_WINDOW: &str = "main";
+
+fn renumber(columns: &mut [ColumnHandle]) {
+    for (index, handle) in columns.iter_mut().enumerate() {
+        handle.column.position = index as u32;
+    }
+}
+
+fn emit_columns(app: &AppHandle, columns: &[ColumnHandle]) {
+    let snapshot: Vec<Column> = columns.iter().map(|h| h.column.clone()).collect();
+    app.emit("columns", snapshot).ok();
+}
+
+/// Resizes and repositions every open column's child webview to an equal
+/// horizontal share of the host window's content area, offset by the current
+/// scroll position. Called whenever a column is added, moved, closed, the
+/// frontend reports a scroll offset, or the host window itself is resized, so
+/// panes stay aligned with their region rather than drifting.
+fn reflow(window: &Window, columns: &[ColumnHandle], scroll_offset: f64) -> Result<(), String> {
+    let count = columns.len();
+    if count == 0 {
+        return Ok(());
+    }
+
+    let scale = window.scale_factor().map_err(|e| e.to_string())?;
+    let content_size = window
+        .inner_size()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+    let column_width = content_size.width / count as f64;
+
+    for handle in columns {
+        let x = handle.column.position as f64 * column_width - scroll_offset;
+        handle
+            .webview
+            .set_position(LogicalPosition::new(x, 0.0))
+            .map_err(|e| e.to_string())?;
+        handle
+            .webview
+            .set_size(LogicalSize::new(column_width, content_size.height))
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn host_window(app: &AppHandle) -> Result<Window, String> {
+    app.get_window(HOST_WINDOW)
+        .ok_or_else(|| "host window not found".to_string())
+}
+
+/// Re-runs `reflow` for the current columns and scroll offset; hooked up to
+/// the host window's resize event in `main.rs` so child panes stay aligned
+/// with their region when the window is resized.
+pub fn reflow_on_resize(app: &AppHandle) {
+    let Ok(window) = host_window(app) else {
+        return;
+    };
+    let state = app.state::<WorkspaceState>();
+    let columns = state.columns.lock().unwrap();
+    let scroll_offset = *state.scroll_offset.lock().unwrap();
+    reflow(&window, &columns, scroll_offset).ok();
+}
+
+#[tauri::command]
+pub fn create_column(
+    title: String,
+    app: AppHandle,
+    state: tauri::State<'_, WorkspaceState>,
+) -> Result<Column, String> {
+    let window = host_window(&app)?;
+
+    let mut columns = state.columns.lock().unwrap();
+    let mut next_id = state.next_id.lock().unwrap();
+
+    let id = format!("column-{}", *next_id);
+    *next_id += 1;
+    let column = Column {
+        id: id.clone(),
+        title,
+        position: columns.len() as u32,
+    };
+
+    let webview = window
+        .add_child(
+            WebviewBuilder::new(&id, WebviewUrl::App(format!("index.html#/column/{}", id).into())),
+            LogicalPosition::new(0.0, 0.0),
+            LogicalSize::new(1.0, 1.0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    columns.push(ColumnHandle {
+        column: column.clone(),
+        webview,
+    });
+    let scroll_offset = *state.scroll_offset.lock().unwrap();
+    reflow(&window, &columns, scroll_offset)?;
+
+    emit_columns(&app, &columns);
+    Ok(column)
+}
+
+#[tauri::command]
+pub fn move_column(
+    id: String,
+    direction: String,
+    app: AppHandle,
+    state: tauri::State<'_, WorkspaceState>,
+) -> Result<(), String> {
+    let window = host_window(&app)?;
+
+    let mut columns = state.columns.lock().unwrap();
+    let index = columns
+        .iter()
+        .position(|c| c.column.id == id)
+        .ok_or_else(|| format!("no column with id '{}'", id))?;
+
+    let target = match direction.as_str() {
+        "left" => index.checked_sub(1),
+        "right" if index + 1 < columns.len() => Some(index + 1),
+        "right" => None,
+        other => return Err(format!("unknown direction '{}'", other)),
+    };
+
+    if let Some(target) = target {
+        columns.swap(index, target);
+        renumber(&mut columns);
+        let scroll_offset = *state.scroll_offset.lock().unwrap();
+        reflow(&window, &columns, scroll_offset)?;
+        emit_columns(&app, &columns);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_column_title(
+    id: String,
+    title: String,
+    app: AppHandle,
+    state: tauri::State<'_, WorkspaceState>,
+) -> Result<(), String> {
+    let mut columns = state.columns.lock().unwrap();
+    let handle = columns
+        .iter_mut()
+        .find(|c| c.column.id == id)
+        .ok_or_else(|| format!("no column with id '{}'", id))?;
+    handle.column.title = title;
+
+    emit_columns(&app, &columns);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn close_column(
+    id: String,
+    app: AppHandle,
+    state: tauri::State<'_, WorkspaceState>,
+) -> Result<(), String> {
+    let window = host_window(&app)?;
+
+    let mut columns = state.columns.lock().unwrap();
+    let index = columns
+        .iter()
+        .position(|c| c.column.id == id)
+        .ok_or_else(|| format!("no column with id '{}'", id))?;
+    let handle = columns.remove(index);
+    handle.webview.close().map_err(|e| e.to_string())?;
+    renumber(&mut columns);
+
+    let scroll_offset = *state.scroll_offset.lock().unwrap();
+    reflow(&window, &columns, scroll_offset)?;
+    emit_columns(&app, &columns);
+    Ok(())
+}
+
+/// Reports how far the frontend has scrolled the column strip horizontally,
+/// so the native child webviews can be shifted by the same amount and stay
+/// aligned with their host region instead of only tracking window resizes.
+#[tauri::command]
+pub fn set_workspace_scroll_offset(
+    offset: f64,
+    app: AppHandle,
+    state: tauri::State<'_, WorkspaceState>,
+) -> Result<(), String> {
+    *state.scroll_offset.lock().unwrap() = offset;
+
+    let window = host_window(&app)?;
+    let columns = state.columns.lock().unwrap();
+    reflow(&window, &columns, offset)
+}
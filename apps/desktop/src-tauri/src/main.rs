@@ -1,23 +1,170 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use futures_util::StreamExt;
+use reqwest::Url;
+use std::io::{Cursor, Read};
+use std::sync::mpsc::Receiver;
+use std::sync::Mutex;
 use tauri::Manager;
 
 mod commands;
+mod voe_monitor;
 use commands::*;
 
+/// Bridges an async `reqwest` byte stream to the synchronous `Read` the custom
+/// protocol response body needs, so `handle_paper_request` can hand the webview
+/// bytes as they arrive instead of buffering the whole PDF/figure in memory
+/// before responding.
+struct StreamReader {
+    chunks: Receiver<std::io::Result<Vec<u8>>>,
+    current: Cursor<Vec<u8>>,
+}
+
+impl Read for StreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let n = self.current.read(buf)?;
+            if n > 0 {
+                return Ok(n);
+            }
+            match self.chunks.recv() {
+                Ok(Ok(chunk)) => self.current = Cursor::new(chunk),
+                Ok(Err(e)) => return Err(e),
+                Err(_) => return Ok(0),
+            }
+        }
+    }
+}
+
+/// Handles `paper://localhost/<pmid-or-doi>` requests from the webview (the
+/// identifier is read from the URI path, not its host) by proxying the
+/// fetch to the AngstromSCD backend off the main thread, so citation PDFs and
+/// figures can be loaded natively through the shared `AppState` client instead
+/// of over `http://localhost:3001` CORS. The response body is streamed to the
+/// webview chunk-by-chunk as it is downloaded, rather than buffered in full
+/// first, so large PDFs don't sit in memory before anything renders.
+fn handle_paper_request(
+    ctx: tauri::UriSchemeContext<'_, tauri::Wry>,
+    request: tauri::http::Request<Vec<u8>>,
+    responder: tauri::UriSchemeResponder,
+) {
+    let identifier = request.uri().path().trim_start_matches('/').to_string();
+    let state = ctx.app_handle().state::<AppState>();
+    let client = state.client.clone();
+    let url = state.endpoint(&format!("api/literature/paper/{}", identifier));
+
+    tauri::async_runtime::spawn(async move {
+        let response = match client.get(&url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                responder.respond(
+                    tauri::http::Response::builder()
+                        .status(502)
+                        .body(e.to_string().into_bytes())
+                        .unwrap(),
+                );
+                return;
+            }
+        };
+
+        let mime = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+
+        // Bounded so `tx.send` blocks the download once a handful of chunks are
+        // queued, applying backpressure to the fetch instead of reading the whole
+        // PDF/figure into memory ahead of a slow or stalled webview reader.
+        const CHANNEL_CAPACITY: usize = 8;
+        let (tx, rx) = std::sync::mpsc::sync_channel(CHANNEL_CAPACITY);
+        std::thread::spawn(move || {
+            let mut byte_stream = response.bytes_stream();
+            while let Some(chunk) = tauri::async_runtime::block_on(byte_stream.next()) {
+                let chunk = chunk
+                    .map(|bytes| bytes.to_vec())
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()));
+                if tx.send(chunk).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let reader = StreamReader {
+            chunks: rx,
+            current: Cursor::new(Vec::new()),
+        };
+
+        responder.respond(
+            tauri::http::Response::builder()
+                .status(200)
+                .header("Content-Type", mime)
+                .body(Box::new(reader) as Box<dyn Read + Send>)
+                .unwrap(),
+        );
+    });
+}
+
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
+        .register_asynchronous_uri_scheme_protocol("paper", handle_paper_request)
         .setup(|app| {
             let window = app.get_webview_window("main").unwrap();
-            
+
             // Set up liquid glass effect for macOS
             #[cfg(target_os = "macos")]
             {
                 use tauri::window::Color;
                 window.set_background_color(Some(Color(0, 0, 0, 0))).ok();
             }
-            
+
+            // Keep the workspace's child-webview columns aligned with their
+            // host region whenever the main window is resized.
+            let resize_handle = app.handle().clone();
+            window.on_window_event(move |event| {
+                if let tauri::WindowEvent::Resized(_) = event {
+                    reflow_on_resize(&resize_handle);
+                }
+            });
+
+            let base_url = std::env::var("ANGSTROMSCD_BACKEND_URL")
+                .unwrap_or_else(|_| "http://localhost:3001/".to_string());
+            let base_url = Url::parse(&base_url).expect("invalid ANGSTROMSCD_BACKEND_URL");
+            app.manage(AppState {
+                client: reqwest::Client::new(),
+                base_url: Mutex::new(base_url),
+            });
+            app.manage(WorkspaceState::default());
+
+            let tray_menu = tauri::menu::MenuBuilder::new(app)
+                .item(&tauri::menu::MenuItemBuilder::with_id(
+                    "voe-alert:none",
+                    "No recent alerts",
+                )
+                .enabled(false)
+                .build(app)?)
+                .separator()
+                .item(&tauri::menu::MenuItemBuilder::with_id(
+                    "voe-toggle-polling",
+                    "Pause alert polling",
+                )
+                .build(app)?)
+                .build()?;
+
+            tauri::tray::TrayIconBuilder::with_id(voe_monitor::TRAY_ID)
+                .tooltip("AngstromSCD VOE Monitor")
+                .icon(app.default_window_icon().unwrap().clone())
+                .menu(&tray_menu)
+                .on_menu_event(|app, event| {
+                    voe_monitor::handle_tray_menu_event(app, event.id().as_ref());
+                })
+                .build(app)?;
+
+            voe_monitor::spawn(app.handle().clone());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -25,7 +172,14 @@ fn main() {
             fetch_api_data,
             search_literature,
             get_voe_alerts,
-            send_chat_message
+            send_chat_message,
+            send_chat_message_stream,
+            set_backend_url,
+            create_column,
+            move_column,
+            set_column_title,
+            close_column,
+            set_workspace_scroll_offset
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
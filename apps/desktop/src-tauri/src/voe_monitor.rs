@@ -0,0 +1,203 @@
+use crate::commands::{AppState, VoeAlert};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::menu::{MenuBuilder, MenuItemBuilder};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// How often the background task checks `/api/voe/alerts` for new risk alerts.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+const HIGH_RISK_LEVEL: &str = "high";
+const RECENT_ALERTS_SHOWN: usize = 5;
+
+/// Tray icon id used so the polling task can look the tray back up via
+/// `AppHandle::tray_by_id` when it needs to refresh the recent-alerts menu.
+pub const TRAY_ID: &str = "voe-monitor";
+
+/// Tracks which alert ids have already been surfaced, the most recent ones
+/// (for the tray menu), the ids of high-risk alerts the user hasn't yet
+/// clicked on from the tray (its size is shown as a badge in the tray title),
+/// and whether background polling is currently turned on. Shared between the
+/// polling task and the tray menu event handler.
+#[derive(Default)]
+pub struct VoeMonitorState {
+    seen_alert_ids: Mutex<HashSet<String>>,
+    recent_alerts: Mutex<Vec<VoeAlert>>,
+    unacknowledged_high_risk: Mutex<HashSet<String>>,
+    polling_enabled: AtomicBool,
+}
+
+impl VoeMonitorState {
+    pub fn is_polling_enabled(&self) -> bool {
+        self.polling_enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_polling_enabled(&self, enabled: bool) {
+        self.polling_enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Spawns the background task that polls VOE alerts, diffs them against what
+/// we've already seen, and for any new high-risk alert fires a native
+/// notification and refreshes the tray's "recent alerts" menu. Runs for the
+/// lifetime of the app.
+pub fn spawn(app: AppHandle) {
+    app.manage(VoeMonitorState {
+        seen_alert_ids: Mutex::new(HashSet::new()),
+        recent_alerts: Mutex::new(Vec::new()),
+        unacknowledged_high_risk: Mutex::new(HashSet::new()),
+        polling_enabled: AtomicBool::new(true),
+    });
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let monitor = app.state::<VoeMonitorState>();
+            if !monitor.is_polling_enabled() {
+                continue;
+            }
+
+            let state = app.state::<AppState>();
+            let url = state.endpoint("api/voe/alerts");
+            let alerts: Vec<VoeAlert> = match state.client.get(&url).send().await {
+                Ok(response) => match response.json().await {
+                    Ok(alerts) => alerts,
+                    Err(_) => continue,
+                },
+                Err(_) => continue,
+            };
+
+            let new_alerts: Vec<VoeAlert> = {
+                let mut seen = monitor.seen_alert_ids.lock().unwrap();
+                alerts
+                    .iter()
+                    .filter(|alert| seen.insert(alert.id.clone()))
+                    .cloned()
+                    .collect()
+            };
+
+            {
+                let mut recent = monitor.recent_alerts.lock().unwrap();
+                recent.splice(0..0, new_alerts.iter().cloned());
+                recent.truncate(RECENT_ALERTS_SHOWN);
+            }
+
+            if new_alerts.is_empty() {
+                continue;
+            }
+
+            app.emit("voe-alerts-updated", &new_alerts).ok();
+
+            for alert in &new_alerts {
+                if alert.risk_level.eq_ignore_ascii_case(HIGH_RISK_LEVEL) {
+                    app.notification()
+                        .builder()
+                        .title("VOE risk alert")
+                        .body(&alert.message)
+                        .show()
+                        .ok();
+                    monitor
+                        .unacknowledged_high_risk
+                        .lock()
+                        .unwrap()
+                        .insert(alert.id.clone());
+                }
+            }
+
+            refresh_tray_menu(&app);
+        }
+    });
+}
+
+/// Rebuilds the tray menu from the current recent-alerts cache and a toggle
+/// entry reflecting the current polling state, and sets the tray's badge to
+/// the number of unacknowledged high-risk alerts. There's no alternate "alert"
+/// icon bundled with the app to swap in, so the badge is rendered via
+/// `TrayIcon::set_title`, which macOS and most Linux tray implementations show
+/// as text next to the icon.
+pub fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let monitor = app.state::<VoeMonitorState>();
+
+    let unacknowledged = monitor.unacknowledged_high_risk.lock().unwrap().len();
+    let badge = if unacknowledged > 0 {
+        Some(unacknowledged.to_string())
+    } else {
+        None
+    };
+    tray.set_title(badge.as_deref()).ok();
+
+    let toggle_label = if monitor.is_polling_enabled() {
+        "Pause alert polling"
+    } else {
+        "Resume alert polling"
+    };
+
+    let recent = monitor.recent_alerts.lock().unwrap().clone();
+    let mut builder = MenuBuilder::new(app);
+    if recent.is_empty() {
+        if let Ok(item) = MenuItemBuilder::with_id("voe-alert:none", "No recent alerts")
+            .enabled(false)
+            .build(app)
+        {
+            builder = builder.item(&item);
+        }
+    } else {
+        for alert in &recent {
+            if let Ok(item) = MenuItemBuilder::with_id(
+                format!("voe-alert:{}", alert.id),
+                format!("{} — {}", alert.patient_id, alert.message),
+            )
+            .build(app)
+            {
+                builder = builder.item(&item);
+            }
+        }
+    }
+
+    if let Ok(toggle) = MenuItemBuilder::with_id("voe-toggle-polling", toggle_label).build(app) {
+        builder = builder.separator().item(&toggle);
+    }
+
+    if let Ok(menu) = builder.build() {
+        tray.set_menu(Some(menu)).ok();
+    }
+}
+
+/// Handles a click on one of the tray menu's dynamic entries: toggles polling,
+/// or emits an event so the frontend can jump to the alert's patient.
+pub fn handle_tray_menu_event(app: &AppHandle, id: &str) {
+    if id == "voe-toggle-polling" {
+        let monitor = app.state::<VoeMonitorState>();
+        monitor.set_polling_enabled(!monitor.is_polling_enabled());
+        refresh_tray_menu(app);
+        return;
+    }
+
+    if let Some(alert_id) = id.strip_prefix("voe-alert:") {
+        if alert_id != "none" {
+            let monitor = app.state::<VoeMonitorState>();
+            let patient_id = monitor
+                .recent_alerts
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|a| a.id == alert_id)
+                .map(|a| a.patient_id.clone());
+            if let Some(patient_id) = patient_id {
+                monitor
+                    .unacknowledged_high_risk
+                    .lock()
+                    .unwrap()
+                    .remove(alert_id);
+                refresh_tray_menu(app);
+                app.emit("voe-alert-focus", patient_id).ok();
+            }
+        }
+    }
+}